@@ -2,8 +2,13 @@ use chrono::offset::Local;
 use chrono::{DateTime, Datelike, Duration, TimeZone};
 use core::panic;
 use dotenv::dotenv;
-use plotters::prelude::{BitMapBackend, CandleStick, ChartBuilder, IntoDrawingArea};
-use plotters::style::{Color, IntoFont, GREEN, RED, WHITE};
+use plotters::prelude::{
+    BitMapBackend, CandleStick, ChartBuilder, Circle, IntoDrawingArea, LineSeries, Polygon,
+};
+use plotters::style::{Color, IntoFont, BLUE, GREEN, MAGENTA, RED, WHITE};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, StandardNormal};
 use std::env;
 use std::fs::create_dir_all;
 use std::path::Path;
@@ -11,6 +16,63 @@ use std::path::Path;
 use exitfailure::ExitFailure;
 use serde_derive::{Deserialize, Serialize};
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Resolution {
+    OneMinute,
+    FiveMinutes,
+    FifteenMinutes,
+    OneHour,
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Resolution {
+    // The value Finnhub expects for the `resolution` query parameter.
+    fn as_finnhub(&self) -> &'static str {
+        match self {
+            Resolution::OneMinute => "1",
+            Resolution::FiveMinutes => "5",
+            Resolution::FifteenMinutes => "15",
+            Resolution::OneHour => "60",
+            Resolution::Daily => "D",
+            Resolution::Weekly => "W",
+            Resolution::Monthly => "M",
+        }
+    }
+
+    // Length of a single candle in seconds, used to floor timestamps into
+    // buckets when aggregating finer candles into coarser ones. The daily,
+    // weekly and monthly variants use nominal lengths (30-day month).
+    fn interval_seconds(&self) -> i64 {
+        match self {
+            Resolution::OneMinute => 60,
+            Resolution::FiveMinutes => 5 * 60,
+            Resolution::FifteenMinutes => 15 * 60,
+            Resolution::OneHour => 60 * 60,
+            Resolution::Daily => 24 * 60 * 60,
+            Resolution::Weekly => 7 * 24 * 60 * 60,
+            Resolution::Monthly => 30 * 24 * 60 * 60,
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self, ExitFailure> {
+        match value {
+            "1m" => Ok(Resolution::OneMinute),
+            "5m" => Ok(Resolution::FiveMinutes),
+            "15m" => Ok(Resolution::FifteenMinutes),
+            "1h" => Ok(Resolution::OneHour),
+            "D" => Ok(Resolution::Daily),
+            "W" => Ok(Resolution::Weekly),
+            "M" => Ok(Resolution::Monthly),
+            other => Err(ExitFailure::from(failure::err_msg(format!(
+                "Unknown resolution '{}', expected one of 1m, 5m, 15m, 1h, D, W, M",
+                other
+            )))),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct StockCandles {
     c: Vec<f64>,
@@ -27,6 +89,7 @@ impl StockCandles {
         symbol: &String,
         from_date: DateTime<Local>,
         to_date: DateTime<Local>,
+        resolution: Resolution,
     ) -> Result<Self, ExitFailure> {
         dotenv().ok();
 
@@ -36,8 +99,8 @@ impl StockCandles {
 
         let url =
             format!(
-            "https://finnhub.io/api/v1/stock/candle?symbol={}&resolution=W&from={}&to={}&token={}",
-            symbol, from_date.timestamp(), to_date.timestamp(), finnhub_api_key
+            "https://finnhub.io/api/v1/stock/candle?symbol={}&resolution={}&from={}&to={}&token={}",
+            symbol, resolution.as_finnhub(), from_date.timestamp(), to_date.timestamp(), finnhub_api_key
         );
 
         println!(
@@ -55,25 +118,697 @@ impl StockCandles {
 
         Ok(response)
     }
+
+    // Merge another series into this one, keeping a single candle per unique
+    // timestamp (the incoming series wins on collision) and sorting by time.
+    fn merge(mut self, other: StockCandles) -> Self {
+        use std::collections::BTreeMap;
+
+        let mut by_time: BTreeMap<i64, (f64, f64, f64, f64, i128)> = BTreeMap::new();
+        let mut insert = |candles: &StockCandles| {
+            for i in 0..candles.t.len() {
+                by_time.insert(
+                    candles.t[i],
+                    (candles.o[i], candles.h[i], candles.l[i], candles.c[i], candles.v[i]),
+                );
+            }
+        };
+        insert(&self);
+        insert(&other);
+
+        self.t.clear();
+        self.o.clear();
+        self.h.clear();
+        self.l.clear();
+        self.c.clear();
+        self.v.clear();
+        for (timestamp, (o, h, l, c, v)) in by_time {
+            self.t.push(timestamp);
+            self.o.push(o);
+            self.h.push(h);
+            self.l.push(l);
+            self.c.push(c);
+            self.v.push(v);
+        }
+        self.s = "ok".to_string();
+        self
+    }
+
+    // Keep only the candles falling within `[from, to]`, so a cached series
+    // wider than the requested window isn't handed to plotting.
+    fn clip(mut self, from: i64, to: i64) -> Self {
+        let keep: Vec<usize> = (0..self.t.len())
+            .filter(|&i| self.t[i] >= from && self.t[i] <= to)
+            .collect();
+        self.t = keep.iter().map(|&i| self.t[i]).collect();
+        self.o = keep.iter().map(|&i| self.o[i]).collect();
+        self.h = keep.iter().map(|&i| self.h[i]).collect();
+        self.l = keep.iter().map(|&i| self.l[i]).collect();
+        self.c = keep.iter().map(|&i| self.c[i]).collect();
+        self.v = keep.iter().map(|&i| self.v[i]).collect();
+        self
+    }
+
+    // Synthesize coarser candles from the fetched series by flooring each
+    // timestamp to the target interval boundary and collapsing every bucket:
+    // open is the first candle's open, close the last candle's close, high the
+    // max high, low the min low and volume the sum of volumes. The bucket's
+    // start timestamp is carried forward so the result is plotted like any
+    // other `StockCandles`.
+    fn aggregate(&self, resolution: Resolution) -> Self {
+        let interval = resolution.interval_seconds();
+
+        let mut t: Vec<i64> = Vec::new();
+        let mut o: Vec<f64> = Vec::new();
+        let mut h: Vec<f64> = Vec::new();
+        let mut l: Vec<f64> = Vec::new();
+        let mut c: Vec<f64> = Vec::new();
+        let mut v: Vec<i128> = Vec::new();
+
+        let mut current_bucket: Option<i64> = None;
+
+        for index in 0..self.t.len() {
+            let bucket = self.t[index] - self.t[index].rem_euclid(interval);
+
+            if current_bucket != Some(bucket) {
+                current_bucket = Some(bucket);
+                t.push(bucket);
+                o.push(self.o[index]);
+                h.push(self.h[index]);
+                l.push(self.l[index]);
+                c.push(self.c[index]);
+                v.push(self.v[index]);
+            } else {
+                let last = t.len() - 1;
+                h[last] = h[last].max(self.h[index]);
+                l[last] = l[last].min(self.l[index]);
+                c[last] = self.c[index];
+                v[last] += self.v[index];
+            }
+        }
+
+        StockCandles {
+            c,
+            h,
+            l,
+            o,
+            s: self.s.clone(),
+            t,
+            v,
+        }
+    }
+}
+
+// A single chart to render, as declared in `charts.toml`. The date range is
+// kept as strings here and parsed into a `ChartSpec` so the config file stays
+// human-friendly (`YYYY-MM-DD`).
+#[derive(Deserialize, Debug)]
+struct ChartEntry {
+    symbol: String,
+    from: String,
+    to: String,
+    resolution: String,
+    #[serde(default)]
+    base_resolution: Option<String>,
+    width: u32,
+    height: u32,
+    output: String,
+    #[serde(default)]
+    projection_days: Option<i64>,
+    #[serde(default)]
+    simulations: Option<usize>,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    update: bool,
+    #[serde(default)]
+    export: Option<String>,
+    #[serde(default)]
+    timestamps: Option<String>,
+    #[serde(default)]
+    adjust: bool,
+}
+
+// Top-level `charts.toml` layout: a list of charts to render in one run.
+#[derive(Deserialize, Debug)]
+struct ChartsConfig {
+    charts: Vec<ChartEntry>,
+}
+
+// A cash dividend event as returned by Finnhub's `/stock/dividend` endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct DividendEvent {
+    date: String,
+    amount: f64,
+}
+
+// A stock split event as returned by Finnhub's `/stock/split` endpoint. The
+// adjustment ratio is `to_factor / from_factor` (2.0 for a 2-for-1 split).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct SplitEvent {
+    date: String,
+    #[serde(rename = "fromFactor")]
+    from_factor: f64,
+    #[serde(rename = "toFactor")]
+    to_factor: f64,
+}
+
+// The corporate actions affecting a symbol over the chart window, sorted
+// chronologically so the back-adjustment can be applied in order.
+struct CorporateActions {
+    dividends: Vec<DividendEvent>,
+    splits: Vec<SplitEvent>,
+}
+
+impl CorporateActions {
+    async fn get(
+        symbol: &String,
+        from_date: DateTime<Local>,
+        to_date: DateTime<Local>,
+    ) -> Result<Self, ExitFailure> {
+        dotenv().ok();
+
+        let finnhub_api_key: String = env::var("FINNHUB_API_KEY")
+            .expect("Error: Finnhub's api key not found.")
+            .to_string();
+
+        let from = from_date.format("%Y-%m-%d").to_string();
+        let to = to_date.format("%Y-%m-%d").to_string();
+
+        let dividend_url = format!(
+            "https://finnhub.io/api/v1/stock/dividend?symbol={}&from={}&to={}&token={}",
+            symbol, from, to, finnhub_api_key
+        );
+        let split_url = format!(
+            "https://finnhub.io/api/v1/stock/split?symbol={}&from={}&to={}&token={}",
+            symbol, from, to, finnhub_api_key
+        );
+
+        println!("Fetching {}'s corporate actions from {} to {}", symbol, from, to);
+
+        let mut dividends = reqwest::get(&dividend_url)
+            .await?
+            .json::<Vec<DividendEvent>>()
+            .await?;
+        let mut splits = reqwest::get(&split_url)
+            .await?
+            .json::<Vec<SplitEvent>>()
+            .await?;
+
+        dividends.sort_by(|a, b| a.date.cmp(&b.date));
+        splits.sort_by(|a, b| a.date.cmp(&b.date));
+
+        Ok(CorporateActions { dividends, splits })
+    }
+}
+
+// Back-adjust the candle prices so long-range charts don't show artificial
+// discontinuities at splits and dividends. Each split divides all prices
+// strictly before its date by the split ratio; each cash dividend multiplies
+// all prior prices by `1 - d / close_prev`, where `close_prev` is the close of
+// the candle immediately preceding the dividend. Returns the epoch timestamps
+// where an action occurred so they can be marked on the chart.
+fn adjust_prices(candles: &mut StockCandles, actions: &CorporateActions) -> Vec<i64> {
+    let mut markers: Vec<i64> = Vec::new();
+
+    // Snapshot the raw close series so each dividend's `close_prev` divides an
+    // as-reported close, unaffected by earlier split scaling of the vector.
+    let raw_close = candles.c.clone();
+
+    let scale_before = |candles: &mut StockCandles, boundary: i64, factor: f64| {
+        for i in 0..candles.t.len() {
+            if candles.t[i] < boundary {
+                candles.o[i] *= factor;
+                candles.h[i] *= factor;
+                candles.l[i] *= factor;
+                candles.c[i] *= factor;
+            }
+        }
+    };
+
+    for split in &actions.splits {
+        if split.from_factor == 0.0 {
+            continue;
+        }
+        let ratio = split.to_factor / split.from_factor;
+        if ratio == 0.0 {
+            continue;
+        }
+        if let Ok(boundary) = parse_date(&split.date) {
+            let boundary = boundary.timestamp();
+            scale_before(candles, boundary, 1.0 / ratio);
+            markers.push(boundary);
+        }
+    }
+
+    for dividend in &actions.dividends {
+        if let Ok(boundary) = parse_date(&dividend.date) {
+            let boundary = boundary.timestamp();
+            // Raw close of the last candle strictly before the dividend date.
+            let close_prev = candles
+                .t
+                .iter()
+                .enumerate()
+                .filter(|&(_, &t)| t < boundary)
+                .last()
+                .map(|(i, _)| raw_close[i]);
+
+            if let Some(close_prev) = close_prev {
+                if close_prev > 0.0 {
+                    scale_before(candles, boundary, 1.0 - dividend.amount / close_prev);
+                    markers.push(boundary);
+                }
+            }
+        }
+    }
+
+    markers.sort_unstable();
+    markers
+}
+
+// Output format for `--export`.
+#[derive(Clone, Copy, Debug)]
+enum ExportFormat {
+    Csv,
+    Json,
+}
+
+// How timestamps are rendered in exported data.
+#[derive(Clone, Copy, Debug)]
+enum TimestampFormat {
+    Epoch,
+    Rfc3339,
+}
+
+// A requested data export alongside the rendered PNG.
+#[derive(Clone, Copy, Debug)]
+struct Export {
+    format: ExportFormat,
+    timestamps: TimestampFormat,
+}
+
+impl ExportFormat {
+    fn parse(value: &str) -> Result<Self, ExitFailure> {
+        match value {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            other => Err(ExitFailure::from(failure::err_msg(format!(
+                "Unknown export format '{}', expected 'csv' or 'json'",
+                other
+            )))),
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+impl TimestampFormat {
+    fn parse(value: &str) -> Result<Self, ExitFailure> {
+        match value {
+            "epoch" => Ok(TimestampFormat::Epoch),
+            "rfc3339" => Ok(TimestampFormat::Rfc3339),
+            other => Err(ExitFailure::from(failure::err_msg(format!(
+                "Unknown timestamp format '{}', expected 'epoch' or 'rfc3339'",
+                other
+            )))),
+        }
+    }
+
+    // Render a raw epoch timestamp as a JSON value in the requested format.
+    fn render(&self, timestamp: i64) -> serde_json::Value {
+        match self {
+            TimestampFormat::Epoch => serde_json::json!(timestamp),
+            TimestampFormat::Rfc3339 => match parse_time(timestamp) {
+                Ok(date) => serde_json::json!(date.to_rfc3339()),
+                Err(_) => serde_json::json!(timestamp),
+            },
+        }
+    }
+}
+
+// Write the fetched candle series to `./static/<symbol>.csv` or `.json` (beside
+// the PNG of a single-symbol run; a chart from `charts.toml` with another
+// output path does not change where the export goes) so it can be fed into
+// spreadsheets or downstream scripts without re-querying the API.
+fn export_candles(
+    symbol: &str,
+    candles: &StockCandles,
+    export: Export,
+) -> Result<(), ExitFailure> {
+    create_directory("./static")?;
+    let path = format!("./static/{}.{}", symbol, export.format.extension());
+
+    let contents = match export.format {
+        ExportFormat::Csv => {
+            let mut out = String::from("timestamp,open,high,low,close,volume\n");
+            for i in 0..candles.t.len() {
+                let timestamp = match export.timestamps {
+                    TimestampFormat::Epoch => candles.t[i].to_string(),
+                    TimestampFormat::Rfc3339 => parse_time(candles.t[i])
+                        .map(|date| date.to_rfc3339())
+                        .unwrap_or_else(|_| candles.t[i].to_string()),
+                };
+                out.push_str(&format!(
+                    "{},{},{},{},{},{}\n",
+                    timestamp, candles.o[i], candles.h[i], candles.l[i], candles.c[i], candles.v[i]
+                ));
+            }
+            out
+        }
+        ExportFormat::Json => {
+            let rows: Vec<serde_json::Value> = (0..candles.t.len())
+                .map(|i| {
+                    serde_json::json!({
+                        "timestamp": export.timestamps.render(candles.t[i]),
+                        "open": candles.o[i],
+                        "high": candles.h[i],
+                        "low": candles.l[i],
+                        "close": candles.c[i],
+                        "volume": candles.v[i],
+                    })
+                })
+                .collect();
+            serde_json::to_string_pretty(&rows)?
+        }
+    };
+
+    std::fs::write(&path, contents)?;
+    println!("Exported {}'s data to {}", symbol, path);
+    Ok(())
+}
+
+// On-disk JSON cache path for a symbol+resolution pair, under `./cache`.
+fn cache_path(symbol: &str, resolution: Resolution) -> String {
+    format!("./cache/{}_{}.json", symbol, resolution.as_finnhub())
+}
+
+// Fetch candles for the window, serving from (and extending) the local cache to
+// avoid re-downloading overlapping ranges. When a cache exists we request only
+// the tail past its last timestamp and merge it in; `--update` forces a full
+// refresh, and a failed request falls back to the cached series when available
+// so already-downloaded symbols stay usable offline.
+async fn fetch_candles(
+    symbol: &String,
+    from_date: DateTime<Local>,
+    to_date: DateTime<Local>,
+    resolution: Resolution,
+    update: bool,
+) -> Result<StockCandles, ExitFailure> {
+    let path = cache_path(symbol, resolution);
+
+    let cached: Option<StockCandles> = if update {
+        None
+    } else {
+        std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+    };
+
+    // Backfill the head and extend the tail so the cache covers the whole
+    // requested window, then clip to `[from_date, to_date]` before serving.
+    let unified = if let Some(mut cached) = cached {
+        // Backfill any history before the cache's start the window asks for.
+        if let Some(&first) = cached.t.first() {
+            if first > from_date.timestamp() {
+                let head_to = parse_time(first).unwrap_or(to_date);
+                match StockCandles::get(symbol, from_date, head_to, resolution).await {
+                    Ok(head) => {
+                        cached = cached.merge(head);
+                        save_cache(&path, &cached)?;
+                    }
+                    Err(e) => {
+                        println!("Backfill failed ({}), {}'s chart may be truncated", e, symbol);
+                    }
+                }
+            }
+        }
+
+        let last = cached.t.last().copied().unwrap_or_else(|| from_date.timestamp());
+        let tail_from = parse_time(last).unwrap_or(from_date);
+
+        if tail_from >= to_date {
+            println!("Serving {} from cache ({})", symbol, path);
+            cached
+        } else {
+            match StockCandles::get(symbol, tail_from, to_date, resolution).await {
+                Ok(fresh) => {
+                    let merged = cached.merge(fresh);
+                    save_cache(&path, &merged)?;
+                    merged
+                }
+                Err(e) => {
+                    println!("Fetch failed ({}), serving {} from cache", e, symbol);
+                    cached
+                }
+            }
+        }
+    } else {
+        let fresh = StockCandles::get(symbol, from_date, to_date, resolution).await?;
+        save_cache(&path, &fresh)?;
+        fresh
+    };
+
+    Ok(unified.clip(from_date.timestamp(), to_date.timestamp()))
+}
+
+fn save_cache(path: &str, candles: &StockCandles) -> Result<(), ExitFailure> {
+    create_directory("./cache")?;
+    std::fs::write(path, serde_json::to_string(candles)?)?;
+    Ok(())
+}
+
+// A forward-looking Monte Carlo projection appended to the historical chart.
+#[derive(Clone, Copy, Debug)]
+struct Projection {
+    days: i64,
+    simulations: usize,
+    seed: Option<u64>,
+}
+
+// A fully-resolved chart request handed to `render_chart`.
+struct ChartSpec {
+    symbol: String,
+    from_date: DateTime<Local>,
+    to_date: DateTime<Local>,
+    // Resolution fetched from Finnhub; `resolution` is the (possibly coarser)
+    // timeframe the chart is rebatched into via `StockCandles::aggregate`.
+    base_resolution: Resolution,
+    resolution: Resolution,
+    width: u32,
+    height: u32,
+    out_file_name: String,
+    projection: Option<Projection>,
+    update: bool,
+    export: Option<Export>,
+    adjust: bool,
+}
+
+impl ChartEntry {
+    fn into_spec(self) -> Result<ChartSpec, ExitFailure> {
+        Ok(ChartSpec {
+            from_date: parse_date(&self.from)?,
+            to_date: parse_date(&self.to)?,
+            base_resolution: match &self.base_resolution {
+                Some(base) => Resolution::parse(base)?,
+                None => Resolution::parse(&self.resolution)?,
+            },
+            resolution: Resolution::parse(&self.resolution)?,
+            width: self.width,
+            height: self.height,
+            out_file_name: self.output,
+            projection: self.projection_days.map(|days| Projection {
+                days,
+                simulations: self.simulations.unwrap_or(1000),
+                seed: self.seed,
+            }),
+            update: self.update,
+            export: match self.export {
+                Some(format) => Some(Export {
+                    format: ExportFormat::parse(&format)?,
+                    timestamps: match self.timestamps {
+                        Some(ts) => TimestampFormat::parse(&ts)?,
+                        None => TimestampFormat::Epoch,
+                    },
+                }),
+                None => None,
+            },
+            adjust: self.adjust,
+            symbol: self.symbol,
+        })
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), ExitFailure> {
     let args: Vec<String> = env::args().collect();
     let mut symbol: String = "AAPL".to_string();
+    let mut resolution: Resolution = Resolution::Weekly;
+    let mut base_resolution: Option<Resolution> = None;
+    let mut config_path: String = "charts.toml".to_string();
+    let mut projection_days: Option<i64> = None;
+    let mut simulations: usize = 1000;
+    let mut seed: Option<u64> = None;
+    let mut update: bool = false;
+    let mut export_format: Option<ExportFormat> = None;
+    let mut timestamp_format: TimestampFormat = TimestampFormat::Epoch;
+    let mut adjust: bool = false;
 
-    if args.len() < 2 {
-        println!("No symbol provided, using default: {}", symbol);
+    // Parse the positional symbol and the optional flags: `--resolution`/`-r`,
+    // `--base-resolution`, `--config`/`-c`, `--project`, `--simulations`,
+    // `--seed`, `--update`, `--export`, `--timestamps` and `--adjust`.
+    let mut positionals = args.iter().skip(1);
+    while let Some(arg) = positionals.next() {
+        match arg.as_str() {
+            "--resolution" | "-r" => {
+                let value = positionals
+                    .next()
+                    .expect("Error: --resolution requires a value");
+                resolution = Resolution::parse(value)?;
+            }
+            "--base-resolution" => {
+                let value = positionals
+                    .next()
+                    .expect("Error: --base-resolution requires a value");
+                base_resolution = Some(Resolution::parse(value)?);
+            }
+            "--config" | "-c" => {
+                config_path = positionals
+                    .next()
+                    .expect("Error: --config requires a value")
+                    .to_string();
+            }
+            "--project" => {
+                let value = positionals
+                    .next()
+                    .expect("Error: --project requires a number of days");
+                projection_days = Some(value.parse().expect("Error: --project expects an integer"));
+            }
+            "--simulations" => {
+                let value = positionals
+                    .next()
+                    .expect("Error: --simulations requires a value");
+                simulations = value.parse().expect("Error: --simulations expects an integer");
+            }
+            "--seed" => {
+                let value = positionals
+                    .next()
+                    .expect("Error: --seed requires a value");
+                seed = Some(value.parse().expect("Error: --seed expects an integer"));
+            }
+            "--update" => update = true,
+            "--export" => {
+                let value = positionals
+                    .next()
+                    .expect("Error: --export requires a format");
+                export_format = Some(ExportFormat::parse(value)?);
+            }
+            "--timestamps" => {
+                let value = positionals
+                    .next()
+                    .expect("Error: --timestamps requires a value");
+                timestamp_format = TimestampFormat::parse(value)?;
+            }
+            "--adjust" => adjust = true,
+            other => symbol = other.to_string(),
+        }
+    }
+
+    // When a config file is present, render every declared chart in one run;
+    // otherwise fall back to the single-symbol default over the last year.
+    if Path::new(&config_path).exists() {
+        let contents = std::fs::read_to_string(&config_path)?;
+        let config: ChartsConfig = toml::from_str(&contents)?;
+        println!(
+            "Loaded {} chart(s) from {}",
+            config.charts.len(),
+            config_path
+        );
+        for entry in config.charts {
+            render_chart(entry.into_spec()?).await?;
+        }
     } else {
-        symbol = args[1].clone();
+        if args.len() < 2 {
+            println!("No symbol provided, using default: {}", symbol);
+        }
+
+        let (from_date, to_date) = (Local::now() - Duration::days(365), Local::now());
+        render_chart(ChartSpec {
+            out_file_name: format!("./static/{}.png", &symbol),
+            symbol,
+            from_date,
+            to_date,
+            base_resolution: base_resolution.unwrap_or(resolution),
+            resolution,
+            width: 1024,
+            height: 768,
+            projection: projection_days.map(|days| Projection {
+                days,
+                simulations,
+                seed,
+            }),
+            update,
+            export: export_format.map(|format| Export {
+                format,
+                timestamps: timestamp_format,
+            }),
+            adjust,
+        })
+        .await?;
     }
 
-    // Fetch stock candles
-    let (from_date, to_date) = (Local::now() - Duration::days(365), Local::now());
+    Ok(())
+}
 
-    let stock_candles = StockCandles::get(&symbol, from_date, to_date).await?;
+// Fetch a symbol's candles for the requested window and resolution and render
+// the candlestick chart to the spec's output path.
+async fn render_chart(spec: ChartSpec) -> Result<(), ExitFailure> {
+    let ChartSpec {
+        symbol,
+        from_date,
+        to_date,
+        base_resolution,
+        resolution,
+        width,
+        height,
+        out_file_name,
+        projection,
+        update,
+        export,
+        adjust,
+    } = spec;
+
+    // Fetch the base resolution once (served from the local cache when
+    // possible) and rebatch it into the target timeframe only when that target
+    // is strictly coarser; otherwise plot the candles at their real timestamps.
+    let fetched = fetch_candles(&symbol, from_date, to_date, base_resolution, update).await?;
 
     println!("{}'s price data fetched successfully", &symbol);
+
+    // Export the raw, as-reported series under `./static` before it is
+    // aggregated or back-adjusted, so downstream consumers get the fetched
+    // OHLCV rather than derived prices.
+    if let Some(export) = export {
+        export_candles(&symbol, &fetched, export)?;
+    }
+
+    let mut stock_candles = if resolution.interval_seconds() > base_resolution.interval_seconds() {
+        fetched.aggregate(resolution)
+    } else {
+        fetched
+    };
+
+    // Back-adjust for dividends and splits so the candles are continuous.
+    let mut action_markers: Vec<i64> = Vec::new();
+    if adjust {
+        let actions = CorporateActions::get(&symbol, from_date, to_date).await?;
+        action_markers = adjust_prices(&mut stock_candles, &actions);
+    }
+
     println!("Plotting {}'s price data", &symbol);
 
     // Collect the data in the stock_candles struct into individual vectors
@@ -98,15 +833,33 @@ async fn main() -> Result<(), ExitFailure> {
         }
     }
 
-    let out_file_name = format!("./static/{}.png", &symbol);
+    // Compute the forward-looking projection (if requested) before sizing the
+    // chart so the axes can be stretched to fit the fan.
+    let last_date = timestamps.last().copied().unwrap_or(to_date);
+    // Spacing of the plotted candles: aggregation only ever makes them coarser.
+    let candle_seconds = resolution
+        .interval_seconds()
+        .max(base_resolution.interval_seconds());
+    let bands: Vec<(DateTime<Local>, f64, f64, f64)> = match projection {
+        Some(projection) => monte_carlo_bands(&close_prices, candle_seconds, last_date, projection),
+        None => Vec::new(),
+    };
+
     create_directory("./static")?;
 
-    let root = BitMapBackend::new(out_file_name.as_str(), (1024, 768)).into_drawing_area();
+    let root =
+        BitMapBackend::new(out_file_name.as_str(), (width, height)).into_drawing_area();
     root.fill(&WHITE)?;
 
-    // Create the chart
-    let highest_price: f64 = high_prices.clone().into_iter().reduce(f64::max).unwrap() + 25.0;
-    let lowest_price: f64 = low_prices.clone().into_iter().reduce(f64::min).unwrap() - 25.0;
+    // Create the chart, extending the axes to cover the projected bands.
+    let mut highest_price: f64 = high_prices.clone().into_iter().reduce(f64::max).unwrap() + 25.0;
+    let mut lowest_price: f64 = low_prices.clone().into_iter().reduce(f64::min).unwrap() - 25.0;
+    let mut x_end = to_date;
+    for &(date, low, _, high) in &bands {
+        highest_price = highest_price.max(high + 25.0);
+        lowest_price = lowest_price.min(low - 25.0);
+        x_end = x_end.max(date);
+    }
 
     let mut chart = ChartBuilder::on(&root)
         .caption(
@@ -116,7 +869,7 @@ async fn main() -> Result<(), ExitFailure> {
         .margin(10)
         .x_label_area_size(50)
         .y_label_area_size(50)
-        .build_cartesian_2d(from_date..to_date, lowest_price..highest_price)?;
+        .build_cartesian_2d(from_date..x_end, lowest_price..highest_price)?;
 
     // Configure the mesh axes
     chart
@@ -155,6 +908,37 @@ async fn main() -> Result<(), ExitFailure> {
                 .expect("Failed to draw series");
         });
 
+    // Mark dividend/split dates with small markers along the bottom axis.
+    if !action_markers.is_empty() {
+        let marker_y = lowest_price;
+        chart
+            .draw_series(action_markers.iter().filter_map(|&timestamp| {
+                parse_time(timestamp)
+                    .ok()
+                    .map(|date| Circle::new((date, marker_y), 4, MAGENTA.filled()))
+            }))
+            .expect("Failed to draw corporate-action markers");
+    }
+
+    // Draw the projection fan to the right of the last real candle: a shaded
+    // 5th-95th percentile region with the median path overlaid.
+    if !bands.is_empty() {
+        let mut polygon: Vec<(DateTime<Local>, f64)> = Vec::with_capacity(bands.len() * 2);
+        polygon.extend(bands.iter().map(|&(date, _, _, high)| (date, high)));
+        polygon.extend(bands.iter().rev().map(|&(date, low, _, _)| (date, low)));
+
+        chart
+            .draw_series(std::iter::once(Polygon::new(polygon, BLUE.mix(0.15))))
+            .expect("Failed to draw projection band");
+
+        chart
+            .draw_series(LineSeries::new(
+                bands.iter().map(|&(date, _, median, _)| (date, median)),
+                BLUE.mix(0.8),
+            ))
+            .expect("Failed to draw projection median");
+    }
+
     root.present().expect(
         "Unable to write result to file, please make sure 'static' dir exists under current dir",
     );
@@ -170,6 +954,94 @@ fn parse_time(timestamp: i64) -> Result<DateTime<Local>, ExitFailure> {
         .ok_or_else(|| ExitFailure::from(failure::err_msg("Failed to parse timestamp")))
 }
 
+// Roll the fetched close prices forward with a geometric random walk and
+// derive per-day 5th/50th/95th percentile bands. Daily log returns drive the
+// drift `mu` and volatility `sigma` (returns between candles `candle_seconds`
+// apart are rescaled to one calendar day); each of `simulations` paths steps
+// `price_{t+1} = price_t * exp(mu + sigma * z)` with `z` a standard-normal
+// draw. Returns one `(date, p5, p50, p95)` tuple per projected day, empty if
+// there is not enough history to estimate returns.
+fn monte_carlo_bands(
+    close_prices: &[f64],
+    candle_seconds: i64,
+    last_date: DateTime<Local>,
+    projection: Projection,
+) -> Vec<(DateTime<Local>, f64, f64, f64)> {
+    let returns: Vec<f64> = close_prices
+        .windows(2)
+        .map(|pair| (pair[1] / pair[0]).ln())
+        .collect();
+
+    // Need at least two returns to estimate a (sample) variance; otherwise the
+    // `n - 1` denominator is zero and `sigma` would be NaN.
+    if returns.len() < 2 {
+        return Vec::new();
+    }
+
+    let n = returns.len() as f64;
+    let mu = returns.iter().sum::<f64>() / n;
+    let variance = returns.iter().map(|r| (r - mu).powi(2)).sum::<f64>() / (n - 1.0);
+
+    // Scale per-candle drift and volatility to a single day's step.
+    let candles_per_day = (24 * 60 * 60) as f64 / candle_seconds as f64;
+    let mu = mu * candles_per_day;
+    let sigma = (variance * candles_per_day).sqrt();
+
+    let last_price = *close_prices.last().unwrap();
+    let days = projection.days.max(0) as usize;
+
+    // Seed from the flag when provided so runs are reproducible, otherwise
+    // fall back to OS entropy.
+    let mut rng = match projection.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    // paths[day] collects every simulation's price on that future day.
+    let mut paths: Vec<Vec<f64>> = vec![Vec::with_capacity(projection.simulations); days];
+    for _ in 0..projection.simulations {
+        let mut price = last_price;
+        for day in paths.iter_mut() {
+            let z: f64 = StandardNormal.sample(&mut rng);
+            price *= (mu + sigma * z).exp();
+            day.push(price);
+        }
+    }
+
+    paths
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut prices)| {
+            prices.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let date = last_date + Duration::days(index as i64 + 1);
+            (
+                date,
+                percentile(&prices, 5.0),
+                percentile(&prices, 50.0),
+                percentile(&prices, 95.0),
+            )
+        })
+        .collect()
+}
+
+// Nearest-rank percentile of an already-sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0 * (sorted.len() as f64 - 1.0)).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
+
+fn parse_date(value: &str) -> Result<DateTime<Local>, ExitFailure> {
+    let naive = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|e| ExitFailure::from(failure::err_msg(format!("Invalid date '{}': {}", value, e))))?
+        .and_hms_opt(0, 0, 0)
+        .ok_or_else(|| ExitFailure::from(failure::err_msg("Failed to build datetime")))?;
+
+    Local
+        .from_local_datetime(&naive)
+        .single()
+        .ok_or_else(|| ExitFailure::from(failure::err_msg("Ambiguous local datetime")))
+}
+
 fn create_directory(dir_name: &str) -> Result<(), ExitFailure> {
     let path = Path::new(dir_name);
 